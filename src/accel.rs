@@ -0,0 +1,173 @@
+//! Hardware-accelerated round diffusion, modeled on ahash's `aes_hash`.
+//!
+//! Fast mode's block compression ends with a diffusion pass that runs one
+//! AES encryption round (`SubBytes` + `ShiftRows` + `MixColumns` +
+//! `AddRoundKey`) per 128-bit lane, once per block after its eight rounds;
+//! the eight rounds themselves do not use AES. This is the same primitive
+//! x86's `AESENC` and AArch64's `AESE`/`AESMC`
+//! instructions compute in hardware. [`aes_round`] picks the fastest
+//! available implementation at runtime via `is_x86_feature_detected!`/
+//! `is_aarch64_feature_detected!`, falling back to a pure-Rust emulation
+//! of the exact same round function on unsupported targets. Because the
+//! scalar path implements the identical AES round definition the hardware
+//! instructions compute, the two are guaranteed to produce identical
+//! output (see the cross-check test below).
+
+/// Run one AES encryption round (`AESENC(state, round_key)`) on a 128-bit
+/// lane, using hardware instructions when the CPU supports them and a
+/// bit-identical scalar fallback otherwise.
+pub(crate) fn aes_round(state: [u8; 16], round_key: [u8; 16]) -> [u8; 16] {
+    #[cfg(target_arch = "x86_64")]
+    {
+        if is_x86_feature_detected!("aes") {
+            return unsafe { aes_round_x86(state, round_key) };
+        }
+    }
+
+    #[cfg(target_arch = "aarch64")]
+    {
+        if std::arch::is_aarch64_feature_detected!("aes") {
+            return unsafe { aes_round_aarch64(state, round_key) };
+        }
+    }
+
+    aes_round_scalar(state, round_key)
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "aes")]
+unsafe fn aes_round_x86(state: [u8; 16], round_key: [u8; 16]) -> [u8; 16] {
+    use std::arch::x86_64::{_mm_aesenc_si128, _mm_loadu_si128, _mm_storeu_si128, __m128i};
+
+    let s = _mm_loadu_si128(state.as_ptr() as *const __m128i);
+    let k = _mm_loadu_si128(round_key.as_ptr() as *const __m128i);
+    let out = _mm_aesenc_si128(s, k);
+
+    let mut result = [0u8; 16];
+    _mm_storeu_si128(result.as_mut_ptr() as *mut __m128i, out);
+    result
+}
+
+#[cfg(target_arch = "aarch64")]
+#[target_feature(enable = "aes")]
+unsafe fn aes_round_aarch64(state: [u8; 16], round_key: [u8; 16]) -> [u8; 16] {
+    use std::arch::aarch64::{vaeseq_u8, vaesmcq_u8, vdupq_n_u8, veorq_u8, vld1q_u8, vst1q_u8};
+
+    // AArch64's crypto extension splits AESENC into `vaeseq_u8` (AddRoundKey
+    // with zero + SubBytes + ShiftRows) and `vaesmcq_u8` (MixColumns), so the
+    // round key has to be XORed in afterwards to match x86's single-op
+    // `AESENC(state, round_key)` semantics.
+    let s = vld1q_u8(state.as_ptr());
+    let sub_shift = vaeseq_u8(s, vdupq_n_u8(0));
+    let mixed = vaesmcq_u8(sub_shift);
+    let k = vld1q_u8(round_key.as_ptr());
+    let out = veorq_u8(mixed, k);
+
+    let mut result = [0u8; 16];
+    vst1q_u8(result.as_mut_ptr(), out);
+    result
+}
+
+fn aes_round_scalar(state: [u8; 16], round_key: [u8; 16]) -> [u8; 16] {
+    let subbed = sub_bytes(state);
+    let shifted = shift_rows(subbed);
+    let mixed = mix_columns(shifted);
+
+    let mut result = [0u8; 16];
+    for i in 0..16 {
+        result[i] = mixed[i] ^ round_key[i];
+    }
+    result
+}
+
+fn sub_bytes(state: [u8; 16]) -> [u8; 16] {
+    let mut out = [0u8; 16];
+    for i in 0..16 {
+        out[i] = AES_SBOX[state[i] as usize];
+    }
+    out
+}
+
+fn shift_rows(state: [u8; 16]) -> [u8; 16] {
+    // Column-major AES state (byte i = row i % 4, column i / 4), matching
+    // the layout x86's XMM registers use for AESENC.
+    [
+        state[0], state[5], state[10], state[15], state[4], state[9], state[14], state[3],
+        state[8], state[13], state[2], state[7], state[12], state[1], state[6], state[11],
+    ]
+}
+
+fn mix_columns(state: [u8; 16]) -> [u8; 16] {
+    let mut out = [0u8; 16];
+    for c in 0..4 {
+        let a0 = state[c * 4];
+        let a1 = state[c * 4 + 1];
+        let a2 = state[c * 4 + 2];
+        let a3 = state[c * 4 + 3];
+
+        out[c * 4] = xtime(a0) ^ (xtime(a1) ^ a1) ^ a2 ^ a3;
+        out[c * 4 + 1] = a0 ^ xtime(a1) ^ (xtime(a2) ^ a2) ^ a3;
+        out[c * 4 + 2] = a0 ^ a1 ^ xtime(a2) ^ (xtime(a3) ^ a3);
+        out[c * 4 + 3] = (xtime(a0) ^ a0) ^ a1 ^ a2 ^ xtime(a3);
+    }
+    out
+}
+
+fn xtime(b: u8) -> u8 {
+    if b & 0x80 != 0 {
+        (b << 1) ^ 0x1B
+    } else {
+        b << 1
+    }
+}
+
+#[rustfmt::skip]
+const AES_SBOX: [u8; 256] = [
+    0x63, 0x7c, 0x77, 0x7b, 0xf2, 0x6b, 0x6f, 0xc5, 0x30, 0x01, 0x67, 0x2b, 0xfe, 0xd7, 0xab, 0x76,
+    0xca, 0x82, 0xc9, 0x7d, 0xfa, 0x59, 0x47, 0xf0, 0xad, 0xd4, 0xa2, 0xaf, 0x9c, 0xa4, 0x72, 0xc0,
+    0xb7, 0xfd, 0x93, 0x26, 0x36, 0x3f, 0xf7, 0xcc, 0x34, 0xa5, 0xe5, 0xf1, 0x71, 0xd8, 0x31, 0x15,
+    0x04, 0xc7, 0x23, 0xc3, 0x18, 0x96, 0x05, 0x9a, 0x07, 0x12, 0x80, 0xe2, 0xeb, 0x27, 0xb2, 0x75,
+    0x09, 0x83, 0x2c, 0x1a, 0x1b, 0x6e, 0x5a, 0xa0, 0x52, 0x3b, 0xd6, 0xb3, 0x29, 0xe3, 0x2f, 0x84,
+    0x53, 0xd1, 0x00, 0xed, 0x20, 0xfc, 0xb1, 0x5b, 0x6a, 0xcb, 0xbe, 0x39, 0x4a, 0x4c, 0x58, 0xcf,
+    0xd0, 0xef, 0xaa, 0xfb, 0x43, 0x4d, 0x33, 0x85, 0x45, 0xf9, 0x02, 0x7f, 0x50, 0x3c, 0x9f, 0xa8,
+    0x51, 0xa3, 0x40, 0x8f, 0x92, 0x9d, 0x38, 0xf5, 0xbc, 0xb6, 0xda, 0x21, 0x10, 0xff, 0xf3, 0xd2,
+    0xcd, 0x0c, 0x13, 0xec, 0x5f, 0x97, 0x44, 0x17, 0xc4, 0xa7, 0x7e, 0x3d, 0x64, 0x5d, 0x19, 0x73,
+    0x60, 0x81, 0x4f, 0xdc, 0x22, 0x2a, 0x90, 0x88, 0x46, 0xee, 0xb8, 0x14, 0xde, 0x5e, 0x0b, 0xdb,
+    0xe0, 0x32, 0x3a, 0x0a, 0x49, 0x06, 0x24, 0x5c, 0xc2, 0xd3, 0xac, 0x62, 0x91, 0x95, 0xe4, 0x79,
+    0xe7, 0xc8, 0x37, 0x6d, 0x8d, 0xd5, 0x4e, 0xa9, 0x6c, 0x56, 0xf4, 0xea, 0x65, 0x7a, 0xae, 0x08,
+    0xba, 0x78, 0x25, 0x2e, 0x1c, 0xa6, 0xb4, 0xc6, 0xe8, 0xdd, 0x74, 0x1f, 0x4b, 0xbd, 0x8b, 0x8a,
+    0x70, 0x3e, 0xb5, 0x66, 0x48, 0x03, 0xf6, 0x0e, 0x61, 0x35, 0x57, 0xb9, 0x86, 0xc1, 0x1d, 0x9e,
+    0xe1, 0xf8, 0x98, 0x11, 0x69, 0xd9, 0x8e, 0x94, 0x9b, 0x1e, 0x87, 0xe9, 0xce, 0x55, 0x28, 0xdf,
+    0x8c, 0xa1, 0x89, 0x0d, 0xbf, 0xe6, 0x42, 0x68, 0x41, 0x99, 0x2d, 0x0f, 0xb0, 0x54, 0xbb, 0x16,
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scalar_round_is_deterministic() {
+        let state = [1u8, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16];
+        let key = [0u8; 16];
+        assert_eq!(aes_round_scalar(state, key), aes_round_scalar(state, key));
+    }
+
+    #[test]
+    fn hardware_path_matches_scalar_path() {
+        // On targets without AES-NI / crypto extensions, `aes_round`
+        // already falls back to the scalar path and this is a tautology;
+        // on targets with hardware support it is the real cross-check.
+        let cases = [
+            ([0u8; 16], [0u8; 16]),
+            ([0xFFu8; 16], [0u8; 16]),
+            (
+                [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16],
+                [16, 15, 14, 13, 12, 11, 10, 9, 8, 7, 6, 5, 4, 3, 2, 1],
+            ),
+        ];
+
+        for (state, key) in cases {
+            assert_eq!(aes_round(state, key), aes_round_scalar(state, key));
+        }
+    }
+}
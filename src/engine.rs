@@ -0,0 +1,210 @@
+//! A resumable streaming engine, modeled on the `HashEngine` pattern used
+//! by SHA-256/SHA-512 implementations: a running `state`, a fixed-size
+//! `buffer` for the current partial block, and a running byte `length`.
+//!
+//! Unlike [`crate::ChronoHashState`], [`ChronoHashEngine`] processes every
+//! full block as soon as it arrives (even in [`Mode::Normal`]) and can
+//! snapshot/resume mid-stream via [`ChronoHashEngine::midstate`] /
+//! [`ChronoHashEngine::from_midstate`], so gigabyte-scale streams never
+//! need to be buffered in full.
+//!
+//! That requires one behavioral difference from [`ChronoHash::hash`]:
+//! `calculate_dynamic_rounds` picks [`Mode::Normal`]'s round count from the
+//! *whole* message's unique-byte complexity, which can't be known until
+//! the last byte has arrived. `ChronoHashEngine` therefore fixes the round
+//! count up front instead -- [`ChronoHashEngine::new`] uses a deterministic
+//! default, and [`ChronoHashEngine::with_rounds`] lets callers pick their
+//! own. Streaming `Normal` mode digests will not match
+//! [`ChronoHash::hash`]'s content-adaptive rounds, by design.
+
+use crate::{pad_tail, state_to_bytes, ChronoHash, Mode, BLOCK_SIZE};
+
+/// The round count `ChronoHashEngine::new` uses for `Mode::Normal`: the
+/// same `base_rounds` value `calculate_dynamic_rounds` starts from before
+/// adding complexity-dependent extra rounds.
+const DEFAULT_NORMAL_ROUNDS: usize = 20;
+
+/// A resumable incremental hasher. See the module documentation for how
+/// this differs from [`crate::ChronoHashState`].
+#[derive(Debug, Clone)]
+pub struct ChronoHashEngine {
+    chrono: ChronoHash,
+    rounds: usize,
+    state: [u32; 8],
+    buffer: [u8; BLOCK_SIZE],
+    buffer_len: usize,
+    length: u64,
+}
+
+/// A snapshot of a [`ChronoHashEngine`] that can be stored and later
+/// resumed with [`ChronoHashEngine::from_midstate`].
+#[derive(Debug, Clone)]
+pub struct ChronoHashMidstate {
+    engine: ChronoHashEngine,
+}
+
+impl ChronoHashEngine {
+    /// Start a new engine in `mode`. `Mode::Normal` uses a fixed round
+    /// schedule ([`DEFAULT_NORMAL_ROUNDS`]) rather than content-adaptive
+    /// rounds, since the full message isn't known up front; use
+    /// [`ChronoHashEngine::with_rounds`] to pick a different fixed count.
+    pub fn new(mode: Mode) -> Self {
+        Self::with_rounds(mode, DEFAULT_NORMAL_ROUNDS)
+    }
+
+    /// Start a new engine in `mode` using an explicit round count for
+    /// `Mode::Normal` (ignored in `Mode::Fast`, which always uses its fixed
+    /// 8 rounds).
+    pub fn with_rounds(mode: Mode, rounds: usize) -> Self {
+        Self::from_chrono(ChronoHash::new(mode), rounds)
+    }
+
+    /// Start a new keyed engine (see [`ChronoHash::new_keyed`]), turning
+    /// the streamed hash into a MAC over the fed bytes.
+    pub fn new_keyed(mode: Mode, key: &[u8; 32]) -> Self {
+        Self::with_rounds_keyed(mode, key, DEFAULT_NORMAL_ROUNDS)
+    }
+
+    /// Like [`ChronoHashEngine::new_keyed`], with an explicit `Mode::Normal`
+    /// round count.
+    pub fn with_rounds_keyed(mode: Mode, key: &[u8; 32], rounds: usize) -> Self {
+        Self::from_chrono(ChronoHash::new_keyed(mode, key), rounds)
+    }
+
+    fn from_chrono(chrono: ChronoHash, rounds: usize) -> Self {
+        let state = chrono.initial_state();
+        Self {
+            chrono,
+            rounds,
+            state,
+            buffer: [0u8; BLOCK_SIZE],
+            buffer_len: 0,
+            length: 0,
+        }
+    }
+
+    /// Feed more message bytes into the engine, compressing every full
+    /// 512-bit block as soon as it's assembled.
+    pub fn update(&mut self, mut data: &[u8]) {
+        self.length += data.len() as u64;
+
+        if self.buffer_len > 0 {
+            let needed = BLOCK_SIZE - self.buffer_len;
+            let take = needed.min(data.len());
+            self.buffer[self.buffer_len..self.buffer_len + take].copy_from_slice(&data[..take]);
+            self.buffer_len += take;
+            data = &data[take..];
+
+            if self.buffer_len == BLOCK_SIZE {
+                self.process_buffered_block();
+                self.buffer_len = 0;
+            }
+        }
+
+        while data.len() >= BLOCK_SIZE {
+            let mut block = [0u8; BLOCK_SIZE];
+            block.copy_from_slice(&data[..BLOCK_SIZE]);
+            self.state = self.chrono.process_block(self.state, &block, self.rounds);
+            data = &data[BLOCK_SIZE..];
+        }
+
+        self.buffer[self.buffer_len..self.buffer_len + data.len()].copy_from_slice(data);
+        self.buffer_len += data.len();
+    }
+
+    /// Finish hashing and return the 256-bit digest.
+    pub fn finalize(self) -> [u8; 32] {
+        let mut state = self.state;
+        let padded = pad_tail(&self.buffer[..self.buffer_len], self.length);
+
+        for chunk in padded.chunks(BLOCK_SIZE) {
+            let mut block = [0u8; BLOCK_SIZE];
+            block.copy_from_slice(chunk);
+            state = self.chrono.process_block(state, &block, self.rounds);
+        }
+
+        // `process_block` already folds the IV (or key) feed-forward into
+        // its result, same as the one-shot `hash` path.
+        state_to_bytes(state)
+    }
+
+    /// Snapshot the engine's current progress so hashing can be paused and
+    /// resumed later, e.g. across a multi-gigabyte stream read in stages.
+    pub fn midstate(&self) -> ChronoHashMidstate {
+        ChronoHashMidstate {
+            engine: self.clone(),
+        }
+    }
+
+    /// Resume an engine from a previously captured [`ChronoHashMidstate`].
+    pub fn from_midstate(midstate: ChronoHashMidstate) -> Self {
+        midstate.engine
+    }
+
+    fn process_buffered_block(&mut self) {
+        let block = self.buffer;
+        self.state = self.chrono.process_block(self.state, &block, self.rounds);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_itself_across_chunkings() {
+        let message = b"the quick brown fox jumps over the lazy dog, a few times over, a few times over";
+
+        for &chunk_size in &[1usize, 3, 7, 64, 65, 200] {
+            let mut reference = ChronoHashEngine::new(Mode::Fast);
+            reference.update(message);
+            let expected = reference.finalize();
+
+            let mut engine = ChronoHashEngine::new(Mode::Fast);
+            for chunk in message.chunks(chunk_size) {
+                engine.update(chunk);
+            }
+            assert_eq!(engine.finalize(), expected, "chunk_size {}", chunk_size);
+        }
+    }
+
+    #[test]
+    fn midstate_resume_matches_uninterrupted_run() {
+        let message = b"resumable streaming across a paused midstate snapshot";
+
+        let mut uninterrupted = ChronoHashEngine::new(Mode::Normal);
+        uninterrupted.update(message);
+        let expected = uninterrupted.finalize();
+
+        let split = message.len() / 2;
+        let mut engine = ChronoHashEngine::new(Mode::Normal);
+        engine.update(&message[..split]);
+        let saved = engine.midstate();
+
+        let mut resumed = ChronoHashEngine::from_midstate(saved);
+        resumed.update(&message[split..]);
+
+        assert_eq!(resumed.finalize(), expected);
+    }
+
+    #[test]
+    fn keyed_engine_matches_one_shot_keyed_hash() {
+        let key = [0x77u8; 32];
+        let message = b"keyed streaming should match ChronoHash::hash_keyed";
+
+        let mut engine = ChronoHashEngine::new_keyed(Mode::Fast, &key);
+        engine.update(&message[..10]);
+        engine.update(&message[10..]);
+
+        assert_eq!(
+            engine.finalize(),
+            ChronoHash::hash_keyed(Mode::Fast, &key, message)
+        );
+    }
+
+    #[test]
+    fn empty_message_finalizes_to_32_bytes() {
+        let engine = ChronoHashEngine::new(Mode::Fast);
+        assert_eq!(engine.finalize().len(), 32);
+    }
+}
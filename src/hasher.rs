@@ -0,0 +1,246 @@
+//! `std::hash::Hasher` / `BuildHasher` adapters for ChronoHash.
+//!
+//! These let `ChronoHash` back a `HashMap`/`HashSet` directly, e.g.
+//! `HashMap<K, V, ChronoRandomState>`, with per-process random seeding so
+//! that hash-flooding ("HashDoS") attacks against externally-controlled
+//! keys are infeasible without knowing the seed. [`ChronoRandomState::new`]
+//! gives HashDoS-resistant random keys, [`ChronoRandomState::with_seed`]
+//! and [`ChronoRandomState::with_seeds`] give deterministic, reproducible
+//! keys for tests and golden-file comparisons.
+
+use crate::{ChronoHash, Mode};
+use std::cell::Cell;
+use std::hash::{BuildHasher, Hasher};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// A `std::hash::Hasher` backed by ChronoHash's fast mode.
+///
+/// Bytes passed to [`write`](Hasher::write) are buffered and hashed as a
+/// single message when [`finish`](Hasher::finish) is called, under a key
+/// via [`ChronoHash::new_keyed`] (not a secret-prefix `H(key || data)`
+/// construction, which would be length-extendable given the underlying
+/// hash's Merkle-Damgard-style feed-forward). The 32-byte digest is folded
+/// down to a `u64` by XORing its first two little-endian 8-byte words
+/// (bytes 0..8 and 8..16); the last 16 digest bytes are not used.
+#[derive(Debug, Clone)]
+pub struct ChronoHasher {
+    buffer: Vec<u8>,
+    key: [u8; 32],
+}
+
+impl ChronoHasher {
+    /// Create a hasher keyed with the given seed words.
+    fn with_keys(keys: [u64; 4]) -> Self {
+        let mut key = [0u8; 32];
+        for (chunk, seed) in key.chunks_mut(8).zip(keys) {
+            chunk.copy_from_slice(&seed.to_le_bytes());
+        }
+        Self {
+            buffer: Vec::new(),
+            key,
+        }
+    }
+}
+
+impl Default for ChronoHasher {
+    fn default() -> Self {
+        Self::with_keys([0, 0, 0, 0])
+    }
+}
+
+impl Hasher for ChronoHasher {
+    fn write(&mut self, bytes: &[u8]) {
+        self.buffer.extend_from_slice(bytes);
+    }
+
+    fn finish(&self) -> u64 {
+        let digest = ChronoHash::new_keyed(Mode::Fast, &self.key).hash(&self.buffer);
+        let lo = u64::from_le_bytes(digest[0..8].try_into().unwrap());
+        let hi = u64::from_le_bytes(digest[8..16].try_into().unwrap());
+        lo ^ hi
+    }
+}
+
+/// A `BuildHasher` that seeds each [`ChronoHasher`] with random per-process
+/// keys, modeled on ahash's `RandomState`.
+///
+/// Use [`ChronoRandomState::new`] for HashDoS-resistant random seeding, or
+/// [`ChronoRandomState::with_seeds`] for deterministic, reproducible
+/// seeding (useful in tests and golden-file comparisons).
+#[derive(Debug, Clone, Copy)]
+pub struct ChronoRandomState {
+    keys: [u64; 4],
+}
+
+// Mixed into every freshly generated seed so that distinct `RandomState`s
+// created in quick succession (or on different threads) still diverge.
+static GLOBAL_SEED_COUNTER: AtomicU64 = AtomicU64::new(0x9E3779B97F4A7C15);
+
+thread_local! {
+    static THREAD_SEED: Cell<u64> = const { Cell::new(0x6A09E667F3BCC908) };
+}
+
+impl ChronoRandomState {
+    /// Build a new, randomly-seeded `ChronoRandomState`.
+    ///
+    /// Seeds are derived from the current time, a global atomic counter,
+    /// and a thread-local counter, so instances created back-to-back (even
+    /// on the same thread) produce different keys.
+    pub fn new() -> Self {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_nanos() as u64)
+            .unwrap_or(0);
+        let global = GLOBAL_SEED_COUNTER.fetch_add(0x2545F4914F6CDD1D, Ordering::Relaxed);
+        let thread = THREAD_SEED.with(|cell| {
+            let next = cell.get().wrapping_add(0x9E3779B97F4A7C15);
+            cell.set(next);
+            next
+        });
+        let stack_addr = &nanos as *const u64 as u64;
+
+        Self::with_seeds(
+            nanos ^ global,
+            global.rotate_left(17) ^ thread,
+            thread.rotate_left(29) ^ stack_addr,
+            stack_addr.rotate_left(11) ^ nanos,
+        )
+    }
+
+    /// Build a `ChronoRandomState` from four fixed seed words.
+    ///
+    /// Unlike [`ChronoRandomState::new`], this is fully deterministic and
+    /// is the right choice for reproducible tests or golden-file hashing.
+    pub fn with_seeds(k0: u64, k1: u64, k2: u64, k3: u64) -> Self {
+        Self {
+            keys: [k0, k1, k2, k3],
+        }
+    }
+
+    /// Build a `ChronoRandomState` from a single seed word, spread across
+    /// all four key slots. A convenience for the common case (one `u64`
+    /// test seed) where [`ChronoRandomState::with_seeds`]' four independent
+    /// words aren't needed.
+    pub fn with_seed(seed: u64) -> Self {
+        Self::with_seeds(
+            seed,
+            seed.rotate_left(16) ^ 0x9E3779B97F4A7C15,
+            seed.rotate_left(32) ^ 0x6A09E667F3BCC908,
+            seed.rotate_left(48) ^ 0xBF58476D1CE4E5B9,
+        )
+    }
+}
+
+impl Default for ChronoRandomState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BuildHasher for ChronoRandomState {
+    type Hasher = ChronoHasher;
+
+    fn build_hasher(&self) -> ChronoHasher {
+        ChronoHasher::with_keys(self.keys)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn hash_map_with_fixed_seeds_is_usable() {
+        let state = ChronoRandomState::with_seeds(1, 2, 3, 4);
+        let mut map: HashMap<&str, i32, ChronoRandomState> = HashMap::with_hasher(state);
+        map.insert("alpha", 1);
+        map.insert("beta", 2);
+
+        assert_eq!(map.get("alpha"), Some(&1));
+        assert_eq!(map.get("beta"), Some(&2));
+        assert_eq!(map.get("gamma"), None);
+    }
+
+    #[test]
+    fn fixed_seeds_are_deterministic() {
+        let a = ChronoRandomState::with_seeds(7, 8, 9, 10).build_hasher();
+        let b = ChronoRandomState::with_seeds(7, 8, 9, 10).build_hasher();
+
+        let mut a = a;
+        let mut b = b;
+        a.write(b"same input");
+        b.write(b"same input");
+        assert_eq!(a.finish(), b.finish());
+    }
+
+    #[test]
+    fn different_seeds_diverge() {
+        let mut a = ChronoRandomState::with_seeds(1, 1, 1, 1).build_hasher();
+        let mut b = ChronoRandomState::with_seeds(2, 2, 2, 2).build_hasher();
+        a.write(b"same input");
+        b.write(b"same input");
+        assert_ne!(a.finish(), b.finish());
+    }
+
+    #[test]
+    fn random_state_instances_diverge() {
+        let a = ChronoRandomState::new();
+        let b = ChronoRandomState::new();
+        assert_ne!(a.keys, b.keys);
+    }
+
+    #[test]
+    fn with_seed_is_deterministic_and_diverges_across_seeds() {
+        let a = ChronoRandomState::with_seed(42).build_hasher();
+        let b = ChronoRandomState::with_seed(42).build_hasher();
+        let mut a = a;
+        let mut b = b;
+        a.write(b"same input");
+        b.write(b"same input");
+        assert_eq!(a.finish(), b.finish());
+
+        let mut c = ChronoRandomState::with_seed(43).build_hasher();
+        c.write(b"same input");
+        assert_ne!(a.finish(), c.finish());
+    }
+
+    // Modeled on ahash's `hash_quality_test`: hashing many distinct keys
+    // under a fixed seed should produce low 64 bits that flip roughly half
+    // their bits between any two keys (avalanche), and should never just
+    // echo the input back out (a "trivial fixed point").
+    #[test]
+    fn hash_quality_avalanches_and_has_no_trivial_fixed_points() {
+        let state = ChronoRandomState::with_seed(0xDEAD_BEEF_CAFE_F00D);
+
+        let mut total_bit_diff = 0u32;
+        let mut comparisons = 0u32;
+        let mut previous: Option<u64> = None;
+
+        for key in 0u64..256 {
+            let mut hasher = state.build_hasher();
+            hasher.write_u64(key);
+            let digest = hasher.finish();
+
+            assert_ne!(digest, key, "trivial fixed point at key {}", key);
+            assert_ne!(digest, 0, "digest collapsed to zero at key {}", key);
+
+            if let Some(prev) = previous {
+                total_bit_diff += (digest ^ prev).count_ones();
+                comparisons += 1;
+            }
+            previous = Some(digest);
+        }
+
+        // Good avalanche means ~32 of 64 bits differ between consecutive
+        // keys on average; allow a generous margin rather than pinning an
+        // exact value.
+        let average_bit_diff = total_bit_diff as f64 / comparisons as f64;
+        assert!(
+            average_bit_diff > 24.0 && average_bit_diff < 40.0,
+            "average bit difference {} outside expected avalanche range",
+            average_bit_diff
+        );
+    }
+}
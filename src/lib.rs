@@ -25,6 +25,19 @@
 
 #![warn(missing_docs)]
 
+mod accel;
+mod engine;
+mod hasher;
+mod streaming;
+mod tree;
+mod xof;
+
+pub use engine::{ChronoHashEngine, ChronoHashMidstate};
+pub use hasher::{ChronoHasher, ChronoRandomState};
+pub use streaming::ChronoHashState;
+pub use tree::{CHUNK_LEN, TREE_CHUNK_LEN};
+pub use xof::XofReader;
+
 /// Operating mode for ChronoHash
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Mode {
@@ -38,6 +51,11 @@ pub enum Mode {
 #[derive(Debug, Clone)]
 pub struct ChronoHash {
     mode: Mode,
+    // `None` for the unkeyed, publicly-specified hash. `Some(key_words)`
+    // turns ChronoHash into a keyed MAC: the key replaces `INITIAL_STATE`
+    // as both the starting state and the final feed-forward value, so the
+    // entire computation depends on the key end to end.
+    key: Option<[u32; 8]>,
 }
 
 // Carefully selected large primes for mixing
@@ -53,7 +71,7 @@ const PRIMES: [u32; 8] = [
 ];
 
 // Initial state vector (derived from e, pi, phi)
-const INITIAL_STATE: [u32; 8] = [
+pub(crate) const INITIAL_STATE: [u32; 8] = [
     0x2B7E1516, 0x28AED2A6, 0xABF71588, 0x09CF4F3C,
     0x762E7160, 0xF38B4DA5, 0x6A09E667, 0xBB67AE85,
 ];
@@ -61,33 +79,153 @@ const INITIAL_STATE: [u32; 8] = [
 // Rotation amounts for each round
 const ROTATIONS: [u32; 16] = [7, 12, 17, 22, 5, 9, 14, 20, 4, 11, 16, 23, 6, 10, 15, 21];
 
-const BLOCK_SIZE: usize = 64; // 512 bits
+pub(crate) const BLOCK_SIZE: usize = 64; // 512 bits
+
+// Folded into `INITIAL_STATE` for `derive_key`'s context-hashing pass, so
+// that pass can never collide with an ordinary `hash()` of the same
+// context bytes, nor with `hash_keyed`'s key-replaces-state construction.
+const DERIVE_KEY_DOMAIN: u32 = 0x4B444646; // "KDFF"
 
 impl ChronoHash {
     /// Create a new ChronoHash instance with the specified mode
     pub fn new(mode: Mode) -> Self {
-        Self { mode }
+        Self { mode, key: None }
+    }
+
+    /// Create a keyed ChronoHash instance, turning the hash into a MAC.
+    ///
+    /// The 32-byte key replaces the public `INITIAL_STATE` as the starting
+    /// state and the final feed-forward value, so every round of the
+    /// computation depends on the key and an attacker without it cannot
+    /// forge or verify digests.
+    pub fn new_keyed(mode: Mode, key: &[u8; 32]) -> Self {
+        let mut key_words = [0u32; 8];
+        for (i, chunk) in key.chunks(4).enumerate() {
+            key_words[i] = u32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]);
+        }
+        Self {
+            mode,
+            key: Some(key_words),
+        }
+    }
+
+    /// Hash `data` under `key`, producing a MAC. Shorthand for
+    /// [`ChronoHash::new_keyed`] followed by [`ChronoHash::hash`].
+    pub fn hash_keyed(mode: Mode, key: &[u8; 32], data: &[u8]) -> [u8; 32] {
+        Self::new_keyed(mode, key).hash(data)
+    }
+
+    /// Derive key material from `key_material`, domain-separated by
+    /// `context`, writing `out.len()` bytes of output.
+    ///
+    /// This mirrors BLAKE3's derive-key mode: `context` is hashed once (in
+    /// [`Mode::Normal`], for maximum diffusion, and under the
+    /// [`DERIVE_KEY_DOMAIN`] flag so this context-hashing pass can never
+    /// collide with an ordinary [`ChronoHash::hash`] of the same context
+    /// bytes) to produce a 32-byte context key. `key_material` is then
+    /// absorbed under that context key via [`ChronoHash::new_keyed`] and
+    /// squeezed out through the same extendable-output construction as
+    /// [`ChronoHash::hash_xof`], so callers can request however many bytes
+    /// of derived key material they need. The same `key_material` under two
+    /// different `context` strings yields independent, uncorrelated
+    /// outputs.
+    pub fn derive_key(context: &str, key_material: &[u8], out: &mut [u8]) {
+        let mut context_start = INITIAL_STATE;
+        context_start[0] ^= DERIVE_KEY_DOMAIN;
+        let context_key = state_to_bytes(
+            ChronoHash::new(Mode::Normal).absorb_from(context_start, context.as_bytes()),
+        );
+
+        ChronoHash::new_keyed(Mode::Normal, &context_key).hash_xof(key_material, out);
+    }
+
+    pub(crate) fn mode(&self) -> Mode {
+        self.mode
+    }
+
+    /// The starting/feed-forward state: the key words when keyed, or the
+    /// public `INITIAL_STATE` otherwise.
+    pub(crate) fn initial_state(&self) -> [u32; 8] {
+        self.key.unwrap_or(INITIAL_STATE)
     }
 
     /// Hash a message and return the 256-bit digest
     pub fn hash(&self, message: &[u8]) -> [u8; 32] {
+        state_to_bytes(self.absorb(message))
+    }
+
+    /// Hash `message` and write `out.len()` bytes of extendable output.
+    ///
+    /// Requesting exactly 32 bytes is identical to [`ChronoHash::hash`].
+    /// Longer outputs are produced by squeezing additional 32-byte blocks:
+    /// the absorbed chaining state is mixed with an incrementing block
+    /// counter through one more compression round, so each output block is
+    /// distinct and requesting `N` bytes is always a prefix of requesting
+    /// any `M > N` bytes.
+    pub fn hash_xof(&self, message: &[u8], out: &mut [u8]) {
+        let absorbed = self.absorb(message);
+
+        let mut counter: u64 = 0;
+        let mut offset = 0;
+        while offset < out.len() {
+            let block = state_to_bytes(self.squeeze_block(absorbed, counter));
+            let take = (out.len() - offset).min(32);
+            out[offset..offset + take].copy_from_slice(&block[..take]);
+            offset += take;
+            counter += 1;
+        }
+    }
+
+    /// Absorb `message` once and return a reader that squeezes out
+    /// extendable output on demand, for callers who don't know how many
+    /// bytes they'll need up front (e.g. filling buffers as they grow).
+    /// Equivalent to repeated [`ChronoHash::hash_xof`] calls over a
+    /// growing output buffer, but without re-absorbing the message.
+    pub fn xof_reader(&self, message: &[u8]) -> XofReader {
+        XofReader::new(self.clone(), self.absorb(message))
+    }
+
+    /// Absorb `message` and return the finalized chaining state, before
+    /// conversion to bytes. Shared by [`ChronoHash::hash`],
+    /// [`ChronoHash::hash_xof`] and [`XofReader`].
+    pub(crate) fn absorb(&self, message: &[u8]) -> [u32; 8] {
+        self.absorb_from(self.initial_state(), message)
+    }
+
+    /// Like [`ChronoHash::absorb`], but starting from a caller-supplied
+    /// state instead of [`ChronoHash::initial_state`]. This is how the tree
+    /// mode in the `tree` module domain-separates leaf nodes: it folds a
+    /// leaf tag and chunk index into the starting state before absorbing.
+    pub(crate) fn absorb_from(&self, start_state: [u32; 8], message: &[u8]) -> [u32; 8] {
         let total_rounds = self.calculate_dynamic_rounds(message);
-        let mut state = INITIAL_STATE;
+        let mut state = start_state;
         let padded = self.pad_message(message);
 
-        // Process each 512-bit block
         for chunk in padded.chunks(BLOCK_SIZE) {
             let mut block = [0u8; BLOCK_SIZE];
             block.copy_from_slice(chunk);
             state = self.process_block(state, &block, total_rounds);
         }
 
-        // Convert state to bytes
-        let mut result = [0u8; 32];
-        for (i, &word) in state.iter().enumerate() {
-            result[i * 4..(i + 1) * 4].copy_from_slice(&word.to_le_bytes());
+        state
+    }
+
+    /// Derive the `counter`-th 32-byte output block from the absorbed
+    /// state. `counter == 0` returns `state` unchanged, so the first block
+    /// always matches [`ChronoHash::hash`].
+    pub(crate) fn squeeze_block(&self, state: [u32; 8], counter: u64) -> [u32; 8] {
+        if counter == 0 {
+            return state;
         }
-        result
+
+        let mut data = [0u32; 16];
+        data[0] = counter as u32;
+        data[1] = (counter >> 32) as u32;
+        for (i, word) in data.iter_mut().enumerate().skip(2) {
+            *word = PRIMES[i % PRIMES.len()];
+        }
+
+        self.compression_round(state, &data, counter as usize)
     }
 
     /// Hash a message and return hex string
@@ -96,7 +234,18 @@ impl ChronoHash {
         digest.iter().map(|b| format!("{:02x}", b)).collect()
     }
 
-    fn calculate_dynamic_rounds(&self, data: &[u8]) -> usize {
+    /// Start an incremental hash in this mode.
+    ///
+    /// Feed data via [`ChronoHashState::update`] and call
+    /// [`ChronoHashState::finalize`] to get the digest. The result is
+    /// identical to calling [`ChronoHash::hash`] once the whole message is
+    /// available, no matter how the input was chunked across `update`
+    /// calls.
+    pub fn hasher(&self) -> ChronoHashState {
+        ChronoHashState::new(self.clone())
+    }
+
+    pub(crate) fn calculate_dynamic_rounds(&self, data: &[u8]) -> usize {
         match self.mode {
             Mode::Fast => 8,
             Mode::Normal => {
@@ -250,15 +399,22 @@ impl ChronoHash {
 
         state = [s0, s1, s2, s3, s4, s5, s6, s7];
 
+        // Hardware-accelerated (or scalar-equivalent) diffusion pass: runs
+        // one AES round per 128-bit lane, keyed with the block data, for
+        // extra mixing that's essentially free when AES-NI/crypto
+        // extensions are available.
+        state = aes_diffusion(state, data);
+
         // Final mixing with IV
+        let iv = self.initial_state();
         for i in 0..8 {
-            state[i] = state[i].wrapping_add(INITIAL_STATE[i]);
+            state[i] = state[i].wrapping_add(iv[i]);
         }
 
         state
     }
 
-    fn process_block(&self, mut state: [u32; 8], block: &[u8; 64], total_rounds: usize) -> [u32; 8] {
+    pub(crate) fn process_block(&self, mut state: [u32; 8], block: &[u8; 64], total_rounds: usize) -> [u32; 8] {
         // Convert block to 32-bit words (little-endian)
         let mut data = [0u32; 16];
         for (i, chunk) in block.chunks(4).enumerate() {
@@ -276,8 +432,9 @@ impl ChronoHash {
             }
 
             // Final mixing
+            let iv = self.initial_state();
             for i in 0..8 {
-                state[i] = state[i].wrapping_add(INITIAL_STATE[i]);
+                state[i] = state[i].wrapping_add(iv[i]);
             }
 
             state
@@ -285,6 +442,69 @@ impl ChronoHash {
     }
 }
 
+/// Pad a trailing partial block the same way `ChronoHash::pad_message`
+/// does internally, but using the *total* message length for the
+/// bit-length footer rather than just the tail's length. Shared by the
+/// streaming and engine APIs, which only ever hold a partial block's worth
+/// of bytes at a time.
+pub(crate) fn pad_tail(tail: &[u8], total_len: u64) -> Vec<u8> {
+    let mut padded = tail.to_vec();
+    padded.push(0x80);
+
+    while (padded.len() % BLOCK_SIZE) != (BLOCK_SIZE - 8) {
+        padded.push(0x00);
+    }
+
+    let bit_len = total_len * 8;
+    padded.extend_from_slice(&bit_len.to_be_bytes());
+    padded
+}
+
+pub(crate) fn state_to_bytes(state: [u32; 8]) -> [u8; 32] {
+    let mut result = [0u8; 32];
+    for (i, &word) in state.iter().enumerate() {
+        result[i * 4..(i + 1) * 4].copy_from_slice(&word.to_le_bytes());
+    }
+    result
+}
+
+/// Diffuse the 256-bit state by running one AES round (see [`accel`]) over
+/// each 128-bit lane, keyed with the corresponding half of the block data.
+fn aes_diffusion(state: [u32; 8], data: &[u32; 16]) -> [u32; 8] {
+    let lane0 = words_to_bytes([state[0], state[1], state[2], state[3]]);
+    let lane1 = words_to_bytes([state[4], state[5], state[6], state[7]]);
+    let key0 = words_to_bytes([data[0], data[1], data[2], data[3]]);
+    let key1 = words_to_bytes([data[4], data[5], data[6], data[7]]);
+
+    let out0 = bytes_to_words(accel::aes_round(lane0, key0));
+    let out1 = bytes_to_words(accel::aes_round(lane1, key1));
+
+    [
+        out0[0], out0[1], out0[2], out0[3], out1[0], out1[1], out1[2], out1[3],
+    ]
+}
+
+fn words_to_bytes(words: [u32; 4]) -> [u8; 16] {
+    let mut bytes = [0u8; 16];
+    for (i, word) in words.iter().enumerate() {
+        bytes[i * 4..(i + 1) * 4].copy_from_slice(&word.to_le_bytes());
+    }
+    bytes
+}
+
+fn bytes_to_words(bytes: [u8; 16]) -> [u32; 4] {
+    let mut words = [0u32; 4];
+    for (i, word) in words.iter_mut().enumerate() {
+        *word = u32::from_le_bytes([
+            bytes[i * 4],
+            bytes[i * 4 + 1],
+            bytes[i * 4 + 2],
+            bytes[i * 4 + 3],
+        ]);
+    }
+    words
+}
+
 /// Convenience function to hash data in fast mode
 pub fn hash_fast(data: &[u8]) -> [u8; 32] {
     ChronoHash::new(Mode::Fast).hash(data)
@@ -351,4 +571,34 @@ mod tests {
         // Different modes should produce different hashes
         assert_ne!(hash_fast, hash_normal);
     }
+
+    #[test]
+    fn test_xof_32_bytes_matches_hash() {
+        let hasher = ChronoHash::new(Mode::Normal);
+        let mut out = [0u8; 32];
+        hasher.hash_xof(b"xof test", &mut out);
+        assert_eq!(out, hasher.hash(b"xof test"));
+    }
+
+    #[test]
+    fn test_xof_is_prefix_consistent() {
+        let hasher = ChronoHash::new(Mode::Fast);
+        let mut short = [0u8; 32];
+        let mut long = [0u8; 96];
+        hasher.hash_xof(b"xof prefix test", &mut short);
+        hasher.hash_xof(b"xof prefix test", &mut long);
+        assert_eq!(&long[..32], &short[..]);
+    }
+
+    #[test]
+    fn test_xof_truncates_final_block() {
+        let hasher = ChronoHash::new(Mode::Normal);
+        let mut out = [0u8; 50];
+        hasher.hash_xof(b"odd length xof", &mut out);
+        // Just needs to fill deterministically without panicking or
+        // leaving trailing zero bytes from a short final block.
+        let mut out2 = [0u8; 50];
+        hasher.hash_xof(b"odd length xof", &mut out2);
+        assert_eq!(out, out2);
+    }
 }
@@ -1,4 +1,4 @@
-use chronohash::{ChronoHash, Mode};
+use chronohash::{ChronoHashEngine, Mode};
 use std::env;
 use std::fs;
 use std::io::{self, Read};
@@ -50,33 +50,58 @@ fn main() {
         i += 1;
     }
 
-    let hasher = ChronoHash::new(mode);
-
     if from_file {
-        // Hash file contents
-        match fs::read(&input) {
-            Ok(data) => {
-                let hash = hasher.hash(&data);
-                println!("{}", hex_encode(&hash));
-            }
+        // Stream the file through ChronoHashEngine so arbitrarily large
+        // files can be hashed in constant memory, in either mode.
+        match fs::File::open(&input) {
+            Ok(file) => match hash_reader(&mut io::BufReader::new(file), mode) {
+                Ok(hash) => println!("{}", hex_encode(&hash)),
+                Err(e) => {
+                    eprintln!("Error reading file '{}': {}", input, e);
+                    std::process::exit(1);
+                }
+            },
             Err(e) => {
                 eprintln!("Error reading file '{}': {}", input, e);
                 std::process::exit(1);
             }
         }
     } else if input.is_empty() {
-        // Read from stdin
-        let mut buffer = Vec::new();
-        io::stdin().read_to_end(&mut buffer).expect("Failed to read from stdin");
-        let hash = hasher.hash(&buffer);
+        // Stream stdin the same way.
+        let hash = hash_reader(&mut io::stdin().lock(), mode).expect("Failed to read from stdin");
         println!("{}", hex_encode(&hash));
     } else {
-        // Hash string
-        let hash = hasher.hash(input.as_bytes());
-        println!("{}", hex_encode(&hash));
+        // Hash string. Routed through the same ChronoHashEngine as the
+        // file/stdin paths (rather than ChronoHash::hash) so all three
+        // input methods agree bit-for-bit on the same bytes -- hash()'s
+        // content-adaptive round count for Mode::Normal would otherwise
+        // diverge from the engine's fixed round schedule.
+        let mut engine = ChronoHashEngine::new(mode);
+        engine.update(input.as_bytes());
+        println!("{}", hex_encode(&engine.finalize()));
     }
 }
 
+/// Read from `reader` in fixed-size chunks and hash them incrementally via
+/// [`ChronoHashEngine`], so the whole input never needs to be held in
+/// memory at once, even in `Mode::Normal`.
+fn hash_reader<R: Read>(reader: &mut R, mode: Mode) -> io::Result<[u8; 32]> {
+    const READ_BUF_SIZE: usize = 64 * 1024;
+
+    let mut engine = ChronoHashEngine::new(mode);
+    let mut buffer = [0u8; READ_BUF_SIZE];
+
+    loop {
+        let bytes_read = reader.read(&mut buffer)?;
+        if bytes_read == 0 {
+            break;
+        }
+        engine.update(&buffer[..bytes_read]);
+    }
+
+    Ok(engine.finalize())
+}
+
 fn hex_encode(bytes: &[u8]) -> String {
     bytes.iter().map(|b| format!("{:02x}", b)).collect()
 }
@@ -94,7 +119,7 @@ fn print_help() {
     println!();
     println!("OPTIONS:");
     println!("    -f, --fast          Use fast mode (8 rounds, ~1M+ h/s)");
-    println!("    -n, --normal        Use normal mode (20-32 rounds, maximum security) [default]");
+    println!("    -n, --normal        Use normal mode (fixed round schedule, maximum security) [default]");
     println!("    --file <FILE>       Hash contents of FILE");
     println!("    -h, --help          Print help information");
     println!("    -v, --version       Print version information");
@@ -106,8 +131,9 @@ fn print_help() {
     println!("    echo \"Hello\" | chronohash-cli");
     println!();
     println!("MODES:");
-    println!("    Normal Mode: 20-32 dynamic rounds based on input complexity");
-    println!("                 Maximum security with temporal diffusion");
+    println!("    Normal Mode: fixed round schedule, streamed in constant memory");
+    println!("                 so every input method (argument, --file, stdin)");
+    println!("                 agrees on the same bytes' digest");
     println!("    Fast Mode:   8 fixed rounds with optimized operations");
     println!("                 ~1M+ hashes/second, excellent for performance");
 }
@@ -0,0 +1,118 @@
+//! Incremental (streaming) hashing.
+//!
+//! [`ChronoHashState`] lets callers feed a message in arbitrarily-sized
+//! pieces via [`update`](ChronoHashState::update) instead of assembling the
+//! whole message up front, which is what the CLI and other large-input
+//! callers need.
+
+use crate::{pad_tail, state_to_bytes, ChronoHash, Mode, BLOCK_SIZE};
+
+/// Incremental hashing state produced by [`ChronoHash::hasher`].
+///
+/// In [`Mode::Fast`], whole 512-bit blocks are compressed as soon as they
+/// fill up, so memory use stays bounded regardless of input size. In
+/// [`Mode::Normal`], the round count depends on the full message's unique-byte
+/// complexity (see `calculate_dynamic_rounds`), which can't be known until
+/// every byte has arrived, so the message is buffered in full and hashed at
+/// [`finalize`](ChronoHashState::finalize).
+#[derive(Debug, Clone)]
+pub struct ChronoHashState {
+    chrono: ChronoHash,
+    state: [u32; 8],
+    buffer: Vec<u8>,
+    total_len: u64,
+}
+
+impl ChronoHashState {
+    pub(crate) fn new(chrono: ChronoHash) -> Self {
+        let state = chrono.initial_state();
+        Self {
+            chrono,
+            state,
+            buffer: Vec::new(),
+            total_len: 0,
+        }
+    }
+
+    /// Feed more message bytes into the hash.
+    pub fn update(&mut self, data: &[u8]) {
+        self.total_len += data.len() as u64;
+        self.buffer.extend_from_slice(data);
+
+        if self.chrono.mode() == Mode::Fast {
+            let mut consumed = 0;
+            while self.buffer.len() - consumed >= BLOCK_SIZE {
+                let mut block = [0u8; BLOCK_SIZE];
+                block.copy_from_slice(&self.buffer[consumed..consumed + BLOCK_SIZE]);
+                self.state = self.chrono.process_block(self.state, &block, 0);
+                consumed += BLOCK_SIZE;
+            }
+            self.buffer.drain(..consumed);
+        }
+    }
+
+    /// Finish hashing and return the 256-bit digest.
+    pub fn finalize(self) -> [u8; 32] {
+        match self.chrono.mode() {
+            Mode::Fast => {
+                let mut state = self.state;
+                let padded = pad_tail(&self.buffer, self.total_len);
+                for chunk in padded.chunks(BLOCK_SIZE) {
+                    let mut block = [0u8; BLOCK_SIZE];
+                    block.copy_from_slice(chunk);
+                    state = self.chrono.process_block(state, &block, 0);
+                }
+                state_to_bytes(state)
+            }
+            // Dynamic round selection needs the whole message, so Normal
+            // mode falls back to the one-shot path over the buffered bytes.
+            Mode::Normal => self.chrono.hash(&self.buffer),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check_matches_one_shot(mode: Mode, message: &[u8], chunk_sizes: &[usize]) {
+        let hasher = ChronoHash::new(mode);
+        let expected = hasher.hash(message);
+
+        for &chunk_size in chunk_sizes {
+            let mut state = hasher.hasher();
+            if chunk_size == 0 {
+                state.update(message);
+            } else {
+                for chunk in message.chunks(chunk_size.max(1)) {
+                    state.update(chunk);
+                }
+            }
+            assert_eq!(
+                state.finalize(),
+                expected,
+                "mismatch for mode {:?} chunk_size {}",
+                mode,
+                chunk_size
+            );
+        }
+    }
+
+    #[test]
+    fn fast_mode_streaming_matches_one_shot() {
+        let message = b"the quick brown fox jumps over the lazy dog, repeated a few times, repeated a few times";
+        check_matches_one_shot(Mode::Fast, message, &[0, 1, 3, 7, 64, 65, 128]);
+    }
+
+    #[test]
+    fn normal_mode_streaming_matches_one_shot() {
+        let message = b"the quick brown fox jumps over the lazy dog, repeated a few times, repeated a few times";
+        check_matches_one_shot(Mode::Normal, message, &[0, 1, 3, 7, 64, 65, 128]);
+    }
+
+    #[test]
+    fn empty_message_streaming_matches_one_shot() {
+        check_matches_one_shot(Mode::Fast, b"", &[0, 1]);
+        check_matches_one_shot(Mode::Normal, b"", &[0, 1]);
+    }
+}
@@ -0,0 +1,245 @@
+//! Parallel tree hashing for large inputs.
+//!
+//! [`ChronoHash::hash_parallel`] splits the message into fixed-size chunks,
+//! hashes each chunk independently as a leaf, then combines chaining
+//! values pairwise up a binary tree until a single root remains. A
+//! domain-separation flag is folded into the starting state of both leaf
+//! and parent compressions so that, e.g., a 64-byte message hashed
+//! directly with [`ChronoHash::hash`] can never collide with a two-chunk
+//! parent node over the same bytes.
+//!
+//! [`ChronoHash::hash_tree`] builds on the same leaf/parent structure with
+//! one addition: the final chaining value (whether it came from a single
+//! leaf or the top parent node) is tagged with a third, root-only domain
+//! separator before being returned. That means the root digest can never
+//! double as a valid internal chaining value for a larger tree over a
+//! superset of the same bytes -- a property [`ChronoHash::hash_parallel`]
+//! deliberately doesn't have, since its whole point is that combining two
+//! parallel subtrees' outputs is itself a valid chaining step.
+//!
+//! Both entry points share the same chunking and subtree-hashing logic
+//! (`subtree_root`/`hash_subtree`) and differ only in whether the root
+//! tag is applied afterwards.
+//!
+//! With the `rayon` feature enabled, independent subtrees are hashed
+//! across a thread pool via `rayon::join`; without it, the same recursion
+//! runs serially. Both paths produce identical digests.
+
+use crate::{state_to_bytes, ChronoHash, BLOCK_SIZE};
+
+/// Default leaf chunk size for [`ChronoHash::hash_parallel`].
+pub const CHUNK_LEN: usize = 1024;
+
+/// Default leaf chunk size for [`ChronoHash::hash_tree`], matching common
+/// Merkle tree hashing chunk sizes (16 KiB).
+pub const TREE_CHUNK_LEN: usize = 16 * 1024;
+
+// Domain tags folded into the starting state so leaf nodes, parent nodes,
+// root nodes, and plain `hash()` output can never collide for the same
+// bytes.
+const LEAF_DOMAIN: u32 = 0x4C45_4146; // "LEAF"
+const PARENT_DOMAIN: u32 = 0x5041_5245; // "PARE"
+// Mixed in via `squeeze_block`'s extra compression round, not the starting
+// state directly, since `hash_tree`'s root tag applies after the whole
+// tree (whether its root is a single chunk or a parent node) is combined.
+const ROOT_TAG: u64 = 0x524F_4F54; // "ROOT"
+
+impl ChronoHash {
+    /// Hash `data` with tree hashing using the default chunk size
+    /// ([`CHUNK_LEN`]). Falls back to a single leaf (no parent nodes) for
+    /// inputs of one chunk or fewer.
+    pub fn hash_parallel(&self, data: &[u8]) -> [u8; 32] {
+        self.hash_parallel_with_chunk_len(data, CHUNK_LEN)
+    }
+
+    /// Hash `data` with tree hashing using a caller-chosen leaf chunk size.
+    /// The same `data` and `chunk_len` always produce the same digest,
+    /// whether or not the `rayon` feature is enabled.
+    pub fn hash_parallel_with_chunk_len(&self, data: &[u8], chunk_len: usize) -> [u8; 32] {
+        state_to_bytes(subtree_root(self, data, chunk_len))
+    }
+
+    /// Hash `data` with tree hashing using [`TREE_CHUNK_LEN`], with full
+    /// chunk/parent/root domain separation: unlike [`ChronoHash::hash_parallel`],
+    /// the root node (whether it's a single chunk or the top parent) is
+    /// tagged distinctly, so its output never doubles as a valid internal
+    /// chaining value.
+    pub fn hash_tree(&self, data: &[u8]) -> [u8; 32] {
+        self.hash_tree_with_chunk_len(data, TREE_CHUNK_LEN)
+    }
+
+    /// Like [`ChronoHash::hash_tree`], with a caller-chosen leaf chunk size.
+    pub fn hash_tree_with_chunk_len(&self, data: &[u8], chunk_len: usize) -> [u8; 32] {
+        let pre_root = subtree_root(self, data, chunk_len);
+        state_to_bytes(self.squeeze_block(pre_root, ROOT_TAG))
+    }
+}
+
+/// Split `data` into leaf chunks of `chunk_len` bytes (one empty chunk for
+/// empty input) and hash the resulting subtree. Shared by
+/// [`ChronoHash::hash_parallel_with_chunk_len`] and
+/// [`ChronoHash::hash_tree_with_chunk_len`], which differ only in whether
+/// they apply the root tag to this value afterwards.
+fn subtree_root(chrono: &ChronoHash, data: &[u8], chunk_len: usize) -> [u32; 8] {
+    assert!(chunk_len > 0, "chunk_len must be greater than zero");
+
+    let chunks: Vec<&[u8]> = if data.is_empty() {
+        vec![data]
+    } else {
+        data.chunks(chunk_len).collect()
+    };
+
+    hash_subtree(chrono, &chunks, 0)
+}
+
+fn hash_leaf(chrono: &ChronoHash, chunk: &[u8], chunk_index: u64) -> [u32; 8] {
+    let mut start = chrono.initial_state();
+    start[0] ^= LEAF_DOMAIN;
+    start[6] ^= chunk_index as u32;
+    start[7] ^= (chunk_index >> 32) as u32;
+    chrono.absorb_from(start, chunk)
+}
+
+fn hash_parent(chrono: &ChronoHash, left: [u8; 32], right: [u8; 32]) -> [u32; 8] {
+    let mut block = [0u8; BLOCK_SIZE];
+    block[..32].copy_from_slice(&left);
+    block[32..].copy_from_slice(&right);
+
+    let mut start = chrono.initial_state();
+    start[0] ^= PARENT_DOMAIN;
+
+    let total_rounds = chrono.calculate_dynamic_rounds(&block);
+    chrono.process_block(start, &block, total_rounds)
+}
+
+#[cfg(feature = "rayon")]
+fn hash_subtree(chrono: &ChronoHash, chunks: &[&[u8]], start_index: u64) -> [u32; 8] {
+    if chunks.len() == 1 {
+        return hash_leaf(chrono, chunks[0], start_index);
+    }
+
+    let mid = chunks.len() / 2;
+    let (left_chunks, right_chunks) = chunks.split_at(mid);
+    let (left, right) = rayon::join(
+        || hash_subtree(chrono, left_chunks, start_index),
+        || hash_subtree(chrono, right_chunks, start_index + mid as u64),
+    );
+
+    hash_parent(chrono, state_to_bytes(left), state_to_bytes(right))
+}
+
+#[cfg(not(feature = "rayon"))]
+fn hash_subtree(chrono: &ChronoHash, chunks: &[&[u8]], start_index: u64) -> [u32; 8] {
+    if chunks.len() == 1 {
+        return hash_leaf(chrono, chunks[0], start_index);
+    }
+
+    let mid = chunks.len() / 2;
+    let (left_chunks, right_chunks) = chunks.split_at(mid);
+    let left = hash_subtree(chrono, left_chunks, start_index);
+    let right = hash_subtree(chrono, right_chunks, start_index + mid as u64);
+
+    hash_parent(chrono, state_to_bytes(left), state_to_bytes(right))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Mode;
+
+    /// Shared by the `hash_parallel`/`hash_tree` boundary-length tests
+    /// below: both constructions should be deterministic at every
+    /// "interesting" length around a chunk boundary.
+    fn assert_deterministic_at_boundary_lengths(hash: impl Fn(&[u8], usize) -> [u8; 32]) {
+        let chunk_len = 16;
+
+        for &len in &[0, 1, chunk_len - 1, chunk_len, chunk_len + 1, 2 * chunk_len, 100 * chunk_len] {
+            let data: Vec<u8> = (0..len).map(|i| (i % 251) as u8).collect();
+            let first = hash(&data, chunk_len);
+            let second = hash(&data, chunk_len);
+            assert_eq!(first, second, "non-deterministic at len {}", len);
+        }
+    }
+
+    #[test]
+    fn deterministic_across_interesting_boundary_lengths() {
+        let hasher = ChronoHash::new(Mode::Fast);
+        assert_deterministic_at_boundary_lengths(|data, chunk_len| {
+            hasher.hash_parallel_with_chunk_len(data, chunk_len)
+        });
+    }
+
+    #[test]
+    fn single_chunk_still_uses_leaf_domain_separation() {
+        let hasher = ChronoHash::new(Mode::Normal);
+        let data = b"shorter than one chunk";
+        let tree_hash = hasher.hash_parallel_with_chunk_len(data, 1024);
+        let plain_hash = hasher.hash(data);
+        assert_ne!(
+            tree_hash, plain_hash,
+            "leaf domain separation should differ from plain hash"
+        );
+    }
+
+    #[test]
+    fn two_chunk_tree_matches_manual_parent_combine() {
+        let hasher = ChronoHash::new(Mode::Normal);
+        let data = b"0123456789abcdef0123456789ABCDEF"; // 32 bytes, chunk_len 16 -> 2 chunks
+        let chunk_len = 16;
+        let tree_hash = hasher.hash_parallel_with_chunk_len(data, chunk_len);
+
+        let chunks: Vec<&[u8]> = data.chunks(chunk_len).collect();
+        let left = hash_leaf(&hasher, chunks[0], 0);
+        let right = hash_leaf(&hasher, chunks[1], 1);
+        let manual = hash_parent(&hasher, state_to_bytes(left), state_to_bytes(right));
+        assert_eq!(tree_hash, state_to_bytes(manual));
+    }
+
+    #[test]
+    fn hash_tree_differs_from_hash_parallel_over_same_bytes() {
+        let hasher = ChronoHash::new(Mode::Fast);
+        let data = b"the same bytes fed through two different tree constructions";
+
+        let tree = hasher.hash_tree_with_chunk_len(data, 16);
+        let parallel = hasher.hash_parallel_with_chunk_len(data, 16);
+        assert_ne!(
+            tree, parallel,
+            "root domain separation should distinguish hash_tree from hash_parallel"
+        );
+    }
+
+    #[test]
+    fn hash_tree_differs_from_pre_root_subtree_value() {
+        let hasher = ChronoHash::new(Mode::Normal);
+        let data = b"checking that the root tag actually mixes the state";
+        let chunk_len = 16;
+
+        let chunks: Vec<&[u8]> = data.chunks(chunk_len).collect();
+        let pre_root = hash_subtree(&hasher, &chunks, 0);
+
+        let tree = hasher.hash_tree_with_chunk_len(data, chunk_len);
+        assert_ne!(
+            tree,
+            state_to_bytes(pre_root),
+            "hash_tree must not return the untagged pre-root chaining value"
+        );
+    }
+
+    #[test]
+    fn hash_tree_deterministic_across_interesting_boundary_lengths() {
+        let hasher = ChronoHash::new(Mode::Fast);
+        assert_deterministic_at_boundary_lengths(|data, chunk_len| {
+            hasher.hash_tree_with_chunk_len(data, chunk_len)
+        });
+    }
+
+    #[test]
+    fn hash_tree_default_matches_explicit_chunk_len() {
+        let hasher = ChronoHash::new(Mode::Fast);
+        let data: Vec<u8> = (0..5000u32).map(|i| (i % 251) as u8).collect();
+        assert_eq!(
+            hasher.hash_tree(&data),
+            hasher.hash_tree_with_chunk_len(&data, TREE_CHUNK_LEN)
+        );
+    }
+}
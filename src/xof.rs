@@ -0,0 +1,98 @@
+//! Incremental reader for [`ChronoHash`]'s extendable-output mode.
+//!
+//! [`XofReader`] absorbs a message once, then lets callers pull output
+//! bytes in however many calls they like -- handy when the amount of
+//! keystream or derived key material needed isn't known up front.
+//! Reading `N` bytes and then `M` more always matches a single
+//! [`ChronoHash::hash_xof`] call for `N + M` bytes.
+
+use crate::{state_to_bytes, ChronoHash};
+use std::io::{self, Read};
+
+/// A squeezable reader over a [`ChronoHash`] extendable-output stream. See
+/// [`ChronoHash::xof_reader`].
+#[derive(Debug, Clone)]
+pub struct XofReader {
+    chrono: ChronoHash,
+    absorbed: [u32; 8],
+    position: u64,
+}
+
+impl XofReader {
+    pub(crate) fn new(chrono: ChronoHash, absorbed: [u32; 8]) -> Self {
+        Self {
+            chrono,
+            absorbed,
+            position: 0,
+        }
+    }
+
+    /// How many output bytes have been read so far.
+    pub fn position(&self) -> u64 {
+        self.position
+    }
+
+    /// Fill `buf` with the next `buf.len()` output bytes.
+    pub fn fill(&mut self, buf: &mut [u8]) {
+        let mut filled = 0;
+        while filled < buf.len() {
+            let block_index = self.position / 32;
+            let offset_in_block = (self.position % 32) as usize;
+
+            let block = state_to_bytes(self.chrono.squeeze_block(self.absorbed, block_index));
+            let available = 32 - offset_in_block;
+            let take = available.min(buf.len() - filled);
+
+            buf[filled..filled + take].copy_from_slice(&block[offset_in_block..offset_in_block + take]);
+            filled += take;
+            self.position += take as u64;
+        }
+    }
+}
+
+impl Read for XofReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.fill(buf);
+        Ok(buf.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Mode;
+
+    #[test]
+    fn incremental_reads_are_prefix_consistent_with_hash_xof() {
+        let hasher = ChronoHash::new(Mode::Normal);
+        let message = b"xof reader test message";
+
+        let mut expected = [0u8; 70];
+        hasher.hash_xof(message, &mut expected);
+
+        let mut reader = hasher.xof_reader(message);
+        let mut first = [0u8; 30];
+        let mut second = [0u8; 40];
+        reader.fill(&mut first);
+        reader.fill(&mut second);
+
+        assert_eq!(&expected[..30], &first[..]);
+        assert_eq!(&expected[30..], &second[..]);
+    }
+
+    #[test]
+    fn read_impl_matches_fill() {
+        let hasher = ChronoHash::new(Mode::Fast);
+        let message = b"xof reader via std::io::Read";
+
+        let mut via_fill = hasher.xof_reader(message);
+        let mut via_read = hasher.xof_reader(message);
+
+        let mut a = [0u8; 50];
+        let mut b = [0u8; 50];
+        via_fill.fill(&mut a);
+        via_read.read_exact(&mut b).unwrap();
+
+        assert_eq!(a, b);
+    }
+}
@@ -0,0 +1,82 @@
+//! Boundary-length conformance harness.
+//!
+//! Exercises a fixed list of "interesting" input lengths around internal
+//! block/chunk boundaries, similar in spirit to BLAKE3's `TEST_CASES`, and
+//! checks that the one-shot [`ChronoHash::hash`] and the incremental
+//! [`ChronoHashState`] always agree, regardless of how the input is
+//! chunked across `update` calls. This is the regression gate for the
+//! streaming API: an off-by-one in block buffering would show up here even
+//! though it's invisible to the fixed-string tests. The lengths include the
+//! tree-hashing chunk boundaries, but the tree-hashing functions
+//! themselves are not called here.
+
+use chronohash::{ChronoHash, Mode, CHUNK_LEN};
+
+const BLOCK_LEN: usize = 64;
+
+/// Fixed set of lengths around the interesting internal boundaries: the
+/// first few bytes, the 512-bit block boundary, and the tree-hashing chunk
+/// boundary (at one and two chunks), plus one large multi-chunk input.
+fn test_case_lengths() -> Vec<usize> {
+    let mut lengths: Vec<usize> = (0..=8).collect();
+    lengths.extend([BLOCK_LEN - 1, BLOCK_LEN, BLOCK_LEN + 1]);
+
+    for multiple in [1usize, 2] {
+        let boundary = multiple * CHUNK_LEN;
+        lengths.extend([boundary - 1, boundary, boundary + 1]);
+    }
+
+    lengths.push(100 * CHUNK_LEN);
+    lengths.sort_unstable();
+    lengths.dedup();
+    lengths
+}
+
+/// A deterministic counter pattern: byte `i` is `i % 251`, so the pattern
+/// repeats every 251 bytes.
+fn deterministic_input(len: usize) -> Vec<u8> {
+    (0..len).map(|i| (i % 251) as u8).collect()
+}
+
+fn assert_conforms(mode: Mode, len: usize) {
+    let hasher = ChronoHash::new(mode);
+    let data = deterministic_input(len);
+
+    let expected = hasher.hash(&data);
+    assert_eq!(expected.len(), 32, "digest must always be 32 bytes");
+    assert_eq!(
+        hasher.hash(&data),
+        expected,
+        "hash() is not deterministic at len {}",
+        len
+    );
+
+    for &chunk_size in &[1usize, 3, 7, 16, 31, 64, 129, 1024] {
+        let mut state = hasher.hasher();
+        for chunk in data.chunks(chunk_size) {
+            state.update(chunk);
+        }
+        let streamed = state.finalize();
+
+        assert_eq!(streamed.len(), 32, "streamed digest must always be 32 bytes");
+        assert_eq!(
+            streamed, expected,
+            "mode {:?} len {} chunk_size {}: streaming result diverged from one-shot hash",
+            mode, len, chunk_size
+        );
+    }
+}
+
+#[test]
+fn fast_mode_conforms_at_boundary_lengths() {
+    for len in test_case_lengths() {
+        assert_conforms(Mode::Fast, len);
+    }
+}
+
+#[test]
+fn normal_mode_conforms_at_boundary_lengths() {
+    for len in test_case_lengths() {
+        assert_conforms(Mode::Normal, len);
+    }
+}
@@ -427,3 +427,75 @@ fn test_performance_no_regression() {
         );
     }
 }
+
+#[test]
+fn test_keyed_avalanche_effect() {
+    // Flipping one key bit should change ~50% of output bits, same as
+    // flipping one input bit.
+    let message = b"keyed avalanche test message";
+    let base_key = [0x42u8; 32];
+    let base_hash = ChronoHash::hash_keyed(Mode::Normal, &base_key, message);
+
+    let mut flip_counts = vec![0u32; 256];
+    let mut total_tests = 0;
+
+    for byte_pos in 0..base_key.len() {
+        for bit_pos in 0..8 {
+            let mut modified_key = base_key;
+            modified_key[byte_pos] ^= 1 << bit_pos;
+            let modified_hash = ChronoHash::hash_keyed(Mode::Normal, &modified_key, message);
+
+            for byte_idx in 0..32 {
+                let diff = base_hash[byte_idx] ^ modified_hash[byte_idx];
+                for bit_idx in 0..8 {
+                    if (diff >> bit_idx) & 1 == 1 {
+                        flip_counts[byte_idx * 8 + bit_idx] += 1;
+                    }
+                }
+            }
+            total_tests += 1;
+        }
+    }
+
+    for (bit_idx, &flip_count) in flip_counts.iter().enumerate() {
+        let flip_rate = flip_count as f64 / total_tests as f64;
+        assert!(
+            flip_rate > 0.3 && flip_rate < 0.7,
+            "Bit {} flip rate {:.1}% outside safe range [30%, 70%]",
+            bit_idx,
+            flip_rate * 100.0
+        );
+    }
+}
+
+#[test]
+fn test_keyed_hash_differs_from_unkeyed() {
+    let message = b"some message";
+    let unkeyed = ChronoHash::new(Mode::Normal).hash(message);
+    let keyed = ChronoHash::hash_keyed(Mode::Normal, &[0x13u8; 32], message);
+    assert_ne!(unkeyed, keyed);
+}
+
+#[test]
+fn test_derive_key_is_context_separated() {
+    let key_material = b"shared secret key material";
+    let mut key_a = [0u8; 32];
+    let mut key_b = [0u8; 32];
+    ChronoHash::derive_key("app A v1", key_material, &mut key_a);
+    ChronoHash::derive_key("app B v1", key_material, &mut key_b);
+    let plain_hash = ChronoHash::new(Mode::Normal).hash(key_material);
+
+    assert_ne!(key_a, key_b, "different contexts must yield different subkeys");
+    assert_ne!(key_a, plain_hash, "derive_key must not collide with plain hash");
+}
+
+#[test]
+fn test_derive_key_supports_arbitrary_output_length() {
+    let key_material = b"some key material to derive from";
+    let mut short = [0u8; 16];
+    let mut long = [0u8; 64];
+    ChronoHash::derive_key("streaming context", key_material, &mut short);
+    ChronoHash::derive_key("streaming context", key_material, &mut long);
+
+    assert_eq!(&short[..], &long[..16], "derive_key output must be prefix-consistent");
+}